@@ -1,5 +1,6 @@
-use crate::{Error, InnerMessage, Message};
+use crate::{Error, Message};
 use std::env;
+use std::os::unix::io::{AsRawFd, RawFd};
 use std::path::Path;
 use tokio1::net::UnixDatagram;
 
@@ -15,8 +16,21 @@ impl SdNotify {
     }
 
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
-        let socket = UnixDatagram::unbound()?;
-        socket.connect(path.as_ref())?;
+        let path = path.as_ref();
+        let socket = match crate::abstract_name(path)? {
+            Some(name) => {
+                let addr = std::os::linux::net::SocketAddrExt::from_abstract_name(name)?;
+                let std_socket = std::os::unix::net::UnixDatagram::unbound()?;
+                std_socket.connect_addr(&addr)?;
+                std_socket.set_nonblocking(true)?;
+                UnixDatagram::from_std(std_socket)?
+            }
+            None => {
+                let socket = UnixDatagram::unbound()?;
+                socket.connect(path)?;
+                socket
+            }
+        };
         Ok(SdNotify { socket })
     }
 
@@ -38,15 +52,86 @@ impl SdNotify {
     }
 
     pub async fn state(&mut self, state: Message) -> Result<(), std::io::Error> {
-        match state.0 {
-            InnerMessage::Ready => self.socket.send(b"READY=1").await?,
-            InnerMessage::Status(status) => {
-                self.socket
-                    .send(format!("STATUS={}", status).as_bytes())
-                    .await?
-            }
-            InnerMessage::Watchdog => self.socket.send(b"WATCHDOG=1").await?,
-        };
+        self.socket.send(&state.encode()).await?;
         Ok(())
     }
+
+    /// Pushes open file descriptors into systemd's fd store (`FDSTORE=1`),
+    /// so this service can be restarted without losing them.
+    pub async fn store_fds(&self, name: &str, fds: &[RawFd]) -> Result<(), std::io::Error> {
+        let message = Message::store_fds(name, fds)?;
+        self.sendmsg(&message).await
+    }
+
+    /// Removes previously stored file descriptors from systemd's fd store.
+    pub async fn remove_fds(&self, name: &str) -> Result<(), std::io::Error> {
+        let message = Message::remove_fds(name)?;
+        self.sendmsg(&message).await
+    }
+
+    /// Sends `message` via raw `sendmsg(2)`, passing any attached
+    /// descriptors as `SCM_RIGHTS` ancillary data. The socket is already
+    /// `connect`ed (see `from_path`), so no destination address is needed.
+    async fn sendmsg(&self, message: &Message) -> Result<(), std::io::Error> {
+        loop {
+            self.socket.writable().await?;
+            let payload = message.encode();
+            match self.socket.try_io(tokio1::io::Interest::WRITABLE, || {
+                crate::sendmsg_with_fds(self.socket.as_raw_fd(), None, &payload, message.fds())
+            }) {
+                Ok(sent) if sent == payload.len() => return Ok(()),
+                Ok(sent) => {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::WriteZero,
+                        format!("short sendmsg: sent {} of {} bytes", sent, payload.len()),
+                    ))
+                }
+                Err(ref e) if e.kind() == std::io::ErrorKind::WouldBlock => continue,
+                Err(e) => return Err(e),
+            }
+        }
+    }
+
+    /// Spawns a task that pings the watchdog every `interval / 2` (the
+    /// cadence systemd recommends) until the returned [`WatchdogHandle`] is
+    /// dropped. The task stops pinging after the first ping that fails.
+    /// `interval` is normally the value returned by
+    /// [`crate::SdNotify::watchdog_enabled`].
+    ///
+    /// Fails if `interval` is so small that `interval / 2` rounds down to
+    /// zero, since `tokio1::time::interval` panics on a zero period.
+    pub fn spawn_watchdog(
+        mut self,
+        interval: std::time::Duration,
+    ) -> Result<WatchdogHandle, std::io::Error> {
+        let period = interval / 2;
+        if period.is_zero() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                "watchdog interval must be at least 2 nanoseconds",
+            ));
+        }
+        let task = tokio1::spawn(async move {
+            let mut ticker = tokio1::time::interval(period);
+            ticker.tick().await; // the first tick fires immediately
+            loop {
+                ticker.tick().await;
+                if self.ping_watchdog().await.is_err() {
+                    break;
+                }
+            }
+        });
+        Ok(WatchdogHandle(task))
+    }
+}
+
+/// Handle for the background watchdog keep-alive task spawned by
+/// [`SdNotify::spawn_watchdog`]. Dropping it stops the pings.
+#[derive(Debug)]
+pub struct WatchdogHandle(tokio1::task::JoinHandle<()>);
+
+impl Drop for WatchdogHandle {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
 }
@@ -2,11 +2,11 @@ use std::env;
 use std::path::{Path, PathBuf};
 
 use futures::sink::Sink;
-use futures::{AsyncSink, Poll, StartSend};
+use futures::{Async, AsyncSink, Poll, StartSend};
 use tokio_codec::Encoder;
 use tokio_uds::{UnixDatagram, UnixDatagramFramed};
 
-use crate::{Error, InnerMessage, Message};
+use crate::{Error, Message};
 use bytes::BytesMut;
 
 struct Codec;
@@ -16,20 +16,45 @@ impl Encoder for Codec {
     type Error = std::io::Error;
 
     fn encode(&mut self, item: Self::Item, bytes: &mut BytesMut) -> Result<(), Self::Error> {
-        match item.0 {
-            InnerMessage::Ready => bytes.extend_from_slice(b"READY=1"),
-            InnerMessage::Status(status) => {
-                bytes.extend_from_slice(format!("STATUS={}", status).as_bytes())
+        bytes.extend_from_slice(&item.encode());
+        Ok(())
+    }
+}
+
+/// A connected datagram socket used for abstract-namespace destinations,
+/// which (unlike a plain path) must be addressed by `connect`-ing up front
+/// rather than per-datagram via [`UnixDatagramFramed`].
+struct Connected {
+    socket: UnixDatagram,
+    pending: Option<BytesMut>,
+}
+
+impl Connected {
+    fn poll_complete(&mut self) -> Poll<(), std::io::Error> {
+        if let Some(buf) = &self.pending {
+            match self.socket.poll_send(buf)? {
+                Async::Ready(_) => {
+                    self.pending = None;
+                    Ok(Async::Ready(()))
+                }
+                Async::NotReady => Ok(Async::NotReady),
             }
-            InnerMessage::Watchdog => bytes.extend_from_slice(b"WATCHDOG=1"),
+        } else {
+            Ok(Async::Ready(()))
         }
-        Ok(())
     }
 }
 
+enum Inner {
+    Framed {
+        path: PathBuf,
+        framed: UnixDatagramFramed<PathBuf, Codec>,
+    },
+    Connected(Connected),
+}
+
 pub struct SdNotify {
-    path: PathBuf,
-    framed: UnixDatagramFramed<PathBuf, Codec>,
+    inner: Inner,
 }
 
 impl Sink for SdNotify {
@@ -37,14 +62,29 @@ impl Sink for SdNotify {
     type SinkError = std::io::Error;
 
     fn start_send(&mut self, item: Self::SinkItem) -> StartSend<Self::SinkItem, Self::SinkError> {
-        match self.framed.start_send((item, self.path.clone()))? {
-            AsyncSink::NotReady((item, _)) => Ok(AsyncSink::NotReady(item)),
-            AsyncSink::Ready => Ok(AsyncSink::Ready),
+        match &mut self.inner {
+            Inner::Framed { path, framed } => match framed.start_send((item, path.clone()))? {
+                AsyncSink::NotReady((item, _)) => Ok(AsyncSink::NotReady(item)),
+                AsyncSink::Ready => Ok(AsyncSink::Ready),
+            },
+            Inner::Connected(connected) => {
+                if connected.pending.is_some() && connected.poll_complete()?.is_not_ready() {
+                    return Ok(AsyncSink::NotReady(item));
+                }
+                let mut bytes = BytesMut::new();
+                Codec.encode(item, &mut bytes)?;
+                connected.pending = Some(bytes);
+                connected.poll_complete()?;
+                Ok(AsyncSink::Ready)
+            }
         }
     }
 
     fn poll_complete(&mut self) -> Poll<(), Self::SinkError> {
-        self.framed.poll_complete()
+        match &mut self.inner {
+            Inner::Framed { framed, .. } => framed.poll_complete(),
+            Inner::Connected(connected) => connected.poll_complete(),
+        }
     }
 }
 
@@ -55,13 +95,34 @@ impl SdNotify {
     }
 
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
-        let socket = UnixDatagram::unbound()?;
-        socket.connect(path.as_ref())?;
-        Ok(SdNotify {
-            framed: UnixDatagramFramed::new(socket, Codec),
-            path: path.as_ref().to_path_buf(),
-        })
+        let path = path.as_ref();
+        let inner = match crate::abstract_name(path)? {
+            Some(name) => {
+                let addr = std::os::linux::net::SocketAddrExt::from_abstract_name(name)?;
+                let std_socket = std::os::unix::net::UnixDatagram::unbound()?;
+                std_socket.connect_addr(&addr)?;
+                std_socket.set_nonblocking(true)?;
+                Inner::Connected(Connected {
+                    socket: UnixDatagram::from_std(std_socket)?,
+                    pending: None,
+                })
+            }
+            None => {
+                let socket = UnixDatagram::unbound()?;
+                socket.connect(path)?;
+                Inner::Framed {
+                    framed: UnixDatagramFramed::new(socket, Codec),
+                    path: path.to_path_buf(),
+                }
+            }
+        };
+        Ok(SdNotify { inner })
     }
+
+    // Fd-store support (`store_fds`/`remove_fds`) isn't offered on this
+    // backend: it needs a raw, non-blocking `sendmsg(2)` with a proper
+    // readiness wait, which doesn't fit this `Sink`'s futures-0.1 model.
+    // Use the sync or `async_await` backend instead.
 }
 
 #[cfg(test)]
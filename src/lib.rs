@@ -41,7 +41,10 @@
 //! ```
 
 use std::env;
-use std::os::unix::net::UnixDatagram;
+use std::os::linux::net::SocketAddrExt;
+use std::os::unix::ffi::OsStrExt;
+use std::os::unix::io::{AsRawFd, RawFd};
+use std::os::unix::net::{SocketAddr, UnixDatagram};
 use std::path::{Path, PathBuf};
 
 #[cfg(feature = "async_await")]
@@ -49,46 +52,405 @@ pub mod async_await;
 #[cfg(feature = "async_io")]
 pub mod async_io;
 
-/// Message to send to init system
+/// `sockaddr_un.sun_path` is 108 bytes on Linux; an abstract name occupies it
+/// after the leading NUL, so at most 107 bytes are available for the name.
+const MAX_ABSTRACT_NAME_LEN: usize = 107;
+
+/// If `path` names a Linux abstract-namespace socket (its first byte is `@`),
+/// returns the name with the `@` stripped. Otherwise returns `None`, meaning
+/// `path` should be treated as a regular filesystem path.
+pub(crate) fn abstract_name(path: &Path) -> Result<Option<&[u8]>, Error> {
+    let bytes = path.as_os_str().as_bytes();
+    match bytes.first() {
+        Some(b'@') => {
+            let name = &bytes[1..];
+            if name.len() > MAX_ABSTRACT_NAME_LEN {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    "abstract socket name too long",
+                )
+                .into());
+            }
+            Ok(Some(name))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Opens the socket named by `NOTIFY_SOCKET`, sends `states` as one batched
+/// datagram, and closes the socket immediately — callers who only ever send
+/// a single notification (e.g. `READY=1` at startup) don't need to
+/// construct and hold onto an [`SdNotify`] for the process lifetime.
+///
+/// A no-op (`Ok(())`) if `NOTIFY_SOCKET` isn't set, matching the reference
+/// C API's `sd_notify`. If `unset_env` is true, [`unset_env`] is called
+/// after the socket name is read, clearing `NOTIFY_SOCKET` and the
+/// `WATCHDOG_*` variables so forked children don't accidentally re-notify
+/// or re-arm the watchdog.
+pub fn notify(unset_env: bool, states: &[State]) -> Result<(), Error> {
+    let sockname = match env::var("NOTIFY_SOCKET") {
+        Ok(sockname) => sockname,
+        Err(_) => return Ok(()),
+    };
+    if unset_env {
+        crate::unset_env();
+    }
+    let message = Message::from_states(states)?;
+    SdNotify::from_path(sockname)?.state(message)?;
+    Ok(())
+}
+
+/// Removes `NOTIFY_SOCKET` and the `WATCHDOG_*` variables from this
+/// process's environment, so that children spawned afterwards don't
+/// inherit them and accidentally re-notify or re-arm the watchdog. This
+/// mirrors the `unsetenv` the reference C API performs when asked to.
+pub fn unset_env() {
+    env::remove_var("NOTIFY_SOCKET");
+    env::remove_var("WATCHDOG_PID");
+    env::remove_var("WATCHDOG_USEC");
+}
+
+/// Where a [`Message`] is sent: a regular filesystem path or a Linux
+/// abstract-namespace address (see `unix(7)`), keyed by the abstract name
+/// rather than a resolved `SocketAddr` so it can also be turned into a raw
+/// `sockaddr_un` for `sendmsg(2)`.
+#[derive(Debug)]
+enum Destination {
+    Path(PathBuf),
+    Abstract(Vec<u8>),
+}
+
+impl Destination {
+    /// Builds the `sockaddr_un` needed to address this destination via the
+    /// raw `sendmsg(2)` path used for fd-store messages.
+    fn to_sockaddr_un(&self) -> std::io::Result<(libc::sockaddr_un, libc::socklen_t)> {
+        let mut addr: libc::sockaddr_un = unsafe { std::mem::zeroed() };
+        addr.sun_family = libc::AF_UNIX as libc::sa_family_t;
+        // SAFETY: `sun_path` is a `[c_char; _]`, i.e. bytes with the same
+        // layout as `[u8; _]` on the platforms this crate supports.
+        let sun_path = unsafe {
+            std::slice::from_raw_parts_mut(addr.sun_path.as_mut_ptr() as *mut u8, addr.sun_path.len())
+        };
+
+        let path_len = match self {
+            Destination::Path(path) => {
+                let bytes = path.as_os_str().as_bytes();
+                if bytes.len() >= sun_path.len() {
+                    return Err(std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        "path too long for sockaddr_un",
+                    ));
+                }
+                sun_path[..bytes.len()].copy_from_slice(bytes);
+                bytes.len() + 1 // include the NUL terminator
+            }
+            Destination::Abstract(name) => {
+                // sun_path[0] is left as the NUL that marks an abstract name.
+                sun_path[1..1 + name.len()].copy_from_slice(name);
+                1 + name.len()
+            }
+        };
+
+        let len = std::mem::size_of::<libc::sa_family_t>() + path_len;
+        Ok((addr, len as libc::socklen_t))
+    }
+}
+
+/// A single `VARIABLE=value` assignment understood by `sd_notify(3)`.
+///
+/// Several states can be packed into one datagram via
+/// [`Message::from_states`] or [`MessageBuilder`], matching the way the
+/// reference C API lets callers pass a single newline-separated string.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum State {
+    /// `READY=1` — daemon startup (or reload) is finished.
+    Ready,
+    /// `RELOADING=1` — daemon is reloading its configuration.
+    Reloading,
+    /// `STOPPING=1` — daemon is beginning its shutdown.
+    Stopping,
+    /// `STATUS=<text>` — single-line free-form status string.
+    Status(String),
+    /// `MAINPID=<pid>` — tells the init system the daemon's main PID.
+    MainPid(u32),
+    /// `ERRNO=<n>` — daemon failed with the given `errno`.
+    Errno(i32),
+    /// `BUSERROR=<name>` — daemon failed with the given D-Bus error name.
+    BusError(String),
+    /// `WATCHDOG=1` — keep-alive ping for `WatchdogSec=`.
+    Watchdog,
+    /// `WATCHDOG=trigger` — tells systemd to immediately treat the service
+    /// as failed due to a watchdog timeout.
+    WatchdogTrigger,
+    /// `WATCHDOG_USEC=<n>` — (re)sets the watchdog timeout, in microseconds.
+    WatchdogUsec(u64),
+    /// `EXTEND_TIMEOUT_USEC=<n>` — extends the start/stop/runtime timeout.
+    ExtendTimeoutUsec(u64),
+    /// `FDSTORE=1` / `FDNAME=<name>` — push descriptors into the fd store.
+    /// Built via [`Message::store_fds`]; the descriptors themselves travel
+    /// out-of-band as `SCM_RIGHTS` ancillary data, not in this variant.
+    FdStore(String),
+    /// `FDSTOREREMOVE=1` / `FDNAME=<name>` — drop descriptors previously
+    /// pushed under `name`.
+    FdStoreRemove(String),
+}
+
+impl State {
+    fn validate(&self) -> Result<(), std::io::Error> {
+        // A newline in any free-text field would let its value smuggle in
+        // extra `VARIABLE=value` assignments, since states are joined with
+        // `\n` into a single datagram.
+        match self {
+            State::Status(text) | State::BusError(text) => validate_no_newline(text),
+            State::FdStore(name) | State::FdStoreRemove(name) => validate_fd_name(name),
+            _ => Ok(()),
+        }
+    }
+
+    fn encode(&self, line: &mut String) {
+        use std::fmt::Write;
+
+        match self {
+            State::Ready => line.push_str("READY=1"),
+            State::Reloading => line.push_str("RELOADING=1"),
+            State::Stopping => line.push_str("STOPPING=1"),
+            State::Status(status) => {
+                line.push_str("STATUS=");
+                line.push_str(status);
+            }
+            State::MainPid(pid) => {
+                let _ = write!(line, "MAINPID={}", pid);
+            }
+            State::Errno(errno) => {
+                let _ = write!(line, "ERRNO={}", errno);
+            }
+            State::BusError(name) => {
+                line.push_str("BUSERROR=");
+                line.push_str(name);
+            }
+            State::Watchdog => line.push_str("WATCHDOG=1"),
+            State::WatchdogTrigger => line.push_str("WATCHDOG=trigger"),
+            State::WatchdogUsec(usec) => {
+                let _ = write!(line, "WATCHDOG_USEC={}", usec);
+            }
+            State::ExtendTimeoutUsec(usec) => {
+                let _ = write!(line, "EXTEND_TIMEOUT_USEC={}", usec);
+            }
+            State::FdStore(name) => {
+                line.push_str("FDSTORE=1\nFDNAME=");
+                line.push_str(name);
+            }
+            State::FdStoreRemove(name) => {
+                line.push_str("FDSTOREREMOVE=1\nFDNAME=");
+                line.push_str(name);
+            }
+        }
+    }
+}
+
+/// Rejects a free-text field value containing a newline, which would
+/// otherwise smuggle extra `VARIABLE=value` assignments into the datagram.
+fn validate_no_newline(text: &str) -> Result<(), std::io::Error> {
+    if text.as_bytes().iter().any(|x| *x == b'\n') {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidData,
+            "newline not allowed",
+        ));
+    }
+    Ok(())
+}
+
+/// Validates an `FDNAME` value: not empty, no control characters, no colon
+/// (systemd uses `:` as a separator when listing stored descriptors), and a
+/// reasonable length.
+fn validate_fd_name(name: &str) -> Result<(), std::io::Error> {
+    if name.is_empty() {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "FDNAME must not be empty",
+        ));
+    }
+    if name.len() > 255 {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "FDNAME too long",
+        ));
+    }
+    if name.bytes().any(|b| b == b':' || b.is_ascii_control()) {
+        return Err(std::io::Error::new(
+            std::io::ErrorKind::InvalidInput,
+            "FDNAME must not contain control characters or ':'",
+        ));
+    }
+    Ok(())
+}
+
+/// Message to send to init system.
+///
+/// A `Message` is one or more [`State`] assignments, encoded as a single
+/// `\n`-joined datagram, matching the batching the notify protocol allows.
+/// A message built via [`Message::store_fds`] additionally carries open file
+/// descriptors that must travel as `SCM_RIGHTS` ancillary data alongside it.
 #[derive(Debug)]
-pub struct Message(InnerMessage);
+pub struct Message {
+    states: Vec<State>,
+    fds: Vec<RawFd>,
+}
 
 impl Message {
     /// Tells the init system that daemon startup is finished.
     pub fn ready() -> Self {
-        Message(InnerMessage::Ready)
+        Message {
+            states: vec![State::Ready],
+            fds: Vec::new(),
+        }
     }
 
     /// Passes a single-line status string back to the init system that describes the daemon state.
     pub fn status(status: String) -> Result<Self, std::io::Error> {
-        if status.as_bytes().iter().any(|x| *x == b'\n') {
-            return Err(std::io::Error::new(
-                std::io::ErrorKind::InvalidData,
-                "newline not allowed",
-            ));
-        }
-        Ok(Message(InnerMessage::Status(status)))
+        Self::from_states(&[State::Status(status)])
     }
 
     /// Tells systemd to update the watchdog timestamp.
     /// This is the keep-alive ping that services need to issue in regular
     /// intervals if WatchdogSec= is enabled for it.
     pub fn watchdog() -> Self {
-        Message(InnerMessage::Watchdog)
+        Message {
+            states: vec![State::Watchdog],
+            fds: Vec::new(),
+        }
+    }
+
+    /// Builds a message that pushes `fds` into systemd's fd store under
+    /// `name` (`FDSTORE=1`/`FDNAME=<name>`). The descriptors are sent as
+    /// `SCM_RIGHTS` ancillary data alongside the regular payload.
+    pub fn store_fds(name: &str, fds: &[RawFd]) -> Result<Self, std::io::Error> {
+        let state = State::FdStore(name.to_string());
+        state.validate()?;
+        Ok(Message {
+            states: vec![state],
+            fds: fds.to_vec(),
+        })
+    }
+
+    /// Builds a message that removes descriptors previously stored under
+    /// `name` (`FDSTOREREMOVE=1`/`FDNAME=<name>`).
+    pub fn remove_fds(name: &str) -> Result<Self, std::io::Error> {
+        let state = State::FdStoreRemove(name.to_string());
+        state.validate()?;
+        Ok(Message {
+            states: vec![state],
+            fds: Vec::new(),
+        })
+    }
+
+    /// Builds a message out of several states, to be sent as one batched,
+    /// `\n`-joined datagram.
+    pub fn from_states(states: &[State]) -> Result<Self, std::io::Error> {
+        for state in states {
+            state.validate()?;
+        }
+        Ok(Message {
+            states: states.to_vec(),
+            fds: Vec::new(),
+        })
+    }
+
+    pub(crate) fn encode(&self) -> Vec<u8> {
+        let mut payload = String::new();
+        for (i, state) in self.states.iter().enumerate() {
+            if i > 0 {
+                payload.push('\n');
+            }
+            state.encode(&mut payload);
+        }
+        payload.into_bytes()
+    }
+
+    pub(crate) fn fds(&self) -> &[RawFd] {
+        &self.fds
     }
 }
 
-#[derive(Debug)]
-enum InnerMessage {
-    Ready,
-    Status(String),
-    Watchdog,
+/// Incrementally builds a [`Message`] out of several [`State`] assignments.
+#[derive(Debug, Default)]
+pub struct MessageBuilder {
+    states: Vec<State>,
+}
+
+impl MessageBuilder {
+    pub fn new() -> Self {
+        MessageBuilder::default()
+    }
+
+    pub fn ready(mut self) -> Self {
+        self.states.push(State::Ready);
+        self
+    }
+
+    pub fn reloading(mut self) -> Self {
+        self.states.push(State::Reloading);
+        self
+    }
+
+    pub fn stopping(mut self) -> Self {
+        self.states.push(State::Stopping);
+        self
+    }
+
+    pub fn status(mut self, status: String) -> Self {
+        self.states.push(State::Status(status));
+        self
+    }
+
+    pub fn main_pid(mut self, pid: u32) -> Self {
+        self.states.push(State::MainPid(pid));
+        self
+    }
+
+    pub fn errno(mut self, errno: i32) -> Self {
+        self.states.push(State::Errno(errno));
+        self
+    }
+
+    pub fn bus_error(mut self, name: String) -> Self {
+        self.states.push(State::BusError(name));
+        self
+    }
+
+    pub fn watchdog(mut self) -> Self {
+        self.states.push(State::Watchdog);
+        self
+    }
+
+    pub fn watchdog_trigger(mut self) -> Self {
+        self.states.push(State::WatchdogTrigger);
+        self
+    }
+
+    pub fn watchdog_usec(mut self, usec: u64) -> Self {
+        self.states.push(State::WatchdogUsec(usec));
+        self
+    }
+
+    pub fn extend_timeout_usec(mut self, usec: u64) -> Self {
+        self.states.push(State::ExtendTimeoutUsec(usec));
+        self
+    }
+
+    /// Validates the accumulated states and builds the [`Message`].
+    pub fn build(self) -> Result<Message, std::io::Error> {
+        Message::from_states(&self.states)
+    }
 }
 
 #[derive(Debug)]
 pub enum Error {
     NoSocket,
     Io(std::io::Error),
+    /// A `sendmsg(2)` call (used to attach `SCM_RIGHTS` file descriptors)
+    /// wrote fewer bytes than the encoded message.
+    PartialSend { sent: usize, expected: usize },
 }
 
 impl std::fmt::Display for Error {
@@ -96,6 +458,9 @@ impl std::fmt::Display for Error {
         match self {
             Error::NoSocket => write!(f, "NOTIFY_SOCKET variable not set"),
             Error::Io(err) => write!(f, "{}", err),
+            Error::PartialSend { sent, expected } => {
+                write!(f, "short sendmsg: sent {} of {} bytes", sent, expected)
+            }
         }
     }
 }
@@ -116,7 +481,7 @@ impl std::error::Error for Error {}
 
 pub struct SdNotify {
     socket: UnixDatagram,
-    path: PathBuf,
+    destination: Destination,
 }
 
 impl SdNotify {
@@ -127,8 +492,12 @@ impl SdNotify {
 
     pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self, Error> {
         let socket = UnixDatagram::unbound()?;
-        let path = path.as_ref().to_path_buf();
-        Ok(SdNotify { socket, path })
+        let path = path.as_ref();
+        let destination = match abstract_name(path)? {
+            Some(name) => Destination::Abstract(name.to_vec()),
+            None => Destination::Path(path.to_path_buf()),
+        };
+        Ok(SdNotify { socket, destination })
     }
 
     /// Tells the init system that daemon startup is finished.
@@ -148,20 +517,129 @@ impl SdNotify {
         self.state(Message::watchdog())
     }
 
+    /// Pushes open file descriptors into systemd's fd store (`FDSTORE=1`),
+    /// so this service can be restarted without losing them. See
+    /// `sd_notify(3)`.
+    pub fn store_fds(&self, name: &str, fds: &[RawFd]) -> Result<(), Error> {
+        let message = Message::store_fds(name, fds)?;
+        self.sendmsg(&message)
+    }
+
+    /// Removes previously stored file descriptors from systemd's fd store.
+    pub fn remove_fds(&self, name: &str) -> Result<(), Error> {
+        self.state(Message::remove_fds(name)?)?;
+        Ok(())
+    }
+
+    /// Parses `WATCHDOG_USEC`/`WATCHDOG_PID` from the environment to learn
+    /// whether, and how often, this process must ping the watchdog.
+    ///
+    /// Returns `None` if `WatchdogSec=` isn't enabled for this service, or
+    /// if `WATCHDOG_PID` names a different process (meaning the watchdog
+    /// was set up for someone else, e.g. a parent that then forked us).
+    pub fn watchdog_enabled() -> Option<std::time::Duration> {
+        let usec: u64 = env::var("WATCHDOG_USEC").ok()?.parse().ok()?;
+        if let Ok(pid) = env::var("WATCHDOG_PID") {
+            let pid: u32 = pid.parse().ok()?;
+            if pid != std::process::id() {
+                return None;
+            }
+        }
+        Some(std::time::Duration::from_micros(usec))
+    }
+
     fn state(&self, state: Message) -> Result<(), std::io::Error> {
-        match state.0 {
-            InnerMessage::Ready => self.socket.send_to(b"READY=1", &self.path)?,
-            InnerMessage::Status(status) => self
-                .socket
-                .send_to(format!("STATUS={}", status).as_bytes(), &self.path)?,
-            InnerMessage::Watchdog => self.socket.send_to(b"WATCHDOG=1", &self.path)?,
+        let payload = state.encode();
+        match &self.destination {
+            Destination::Path(path) => self.socket.send_to(&payload, path)?,
+            Destination::Abstract(name) => {
+                let addr = SocketAddr::from_abstract_name(name)?;
+                self.socket.send_to_addr(&payload, &addr)?
+            }
         };
         Ok(())
     }
+
+    /// Sends a [`Message`] carrying file descriptors via `sendmsg(2)` with
+    /// `SCM_RIGHTS` ancillary data, since `UnixDatagram::send_to` cannot
+    /// carry control messages.
+    fn sendmsg(&self, message: &Message) -> Result<(), Error> {
+        let (addr, addr_len) = self.destination.to_sockaddr_un()?;
+        let payload = message.encode();
+        let sent = sendmsg_with_fds(
+            self.socket.as_raw_fd(),
+            Some((&addr, addr_len)),
+            &payload,
+            message.fds(),
+        )?;
+        if sent != payload.len() {
+            return Err(Error::PartialSend {
+                sent,
+                expected: payload.len(),
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Sends `payload` (and, if non-empty, `fds` as `SCM_RIGHTS` ancillary data)
+/// over `fd` via `sendmsg(2)`. `dest` addresses an unconnected socket, or is
+/// `None` to use the socket's already-`connect`ed peer.
+pub(crate) fn sendmsg_with_fds(
+    fd: RawFd,
+    dest: Option<(&libc::sockaddr_un, libc::socklen_t)>,
+    payload: &[u8],
+    fds: &[RawFd],
+) -> std::io::Result<usize> {
+    let mut iov = libc::iovec {
+        iov_base: payload.as_ptr() as *mut libc::c_void,
+        iov_len: payload.len(),
+    };
+
+    let cmsg_space = unsafe { libc::CMSG_SPACE(std::mem::size_of_val(fds) as u32) };
+    // `cmsghdr` requires 8-byte alignment, which a `Vec<u8>` does not
+    // guarantee; back the buffer with `u64`s so `CMSG_FIRSTHDR`'s writes
+    // through a `*mut cmsghdr` are never unaligned.
+    let mut cmsg_buf = vec![0u64; (cmsg_space as usize).div_ceil(std::mem::size_of::<u64>())];
+
+    let mut msg: libc::msghdr = unsafe { std::mem::zeroed() };
+    if let Some((addr, len)) = dest {
+        msg.msg_name = addr as *const libc::sockaddr_un as *mut libc::c_void;
+        msg.msg_namelen = len;
+    }
+    msg.msg_iov = &mut iov;
+    msg.msg_iovlen = 1;
+
+    if !fds.is_empty() {
+        msg.msg_control = cmsg_buf.as_mut_ptr() as *mut libc::c_void;
+        msg.msg_controllen = cmsg_space as _;
+        unsafe {
+            let cmsg = libc::CMSG_FIRSTHDR(&msg);
+            (*cmsg).cmsg_level = libc::SOL_SOCKET;
+            (*cmsg).cmsg_type = libc::SCM_RIGHTS;
+            (*cmsg).cmsg_len = libc::CMSG_LEN(std::mem::size_of_val(fds) as u32) as _;
+            std::ptr::copy_nonoverlapping(fds.as_ptr(), libc::CMSG_DATA(cmsg) as *mut RawFd, fds.len());
+        }
+    }
+
+    let sent = unsafe { libc::sendmsg(fd, &msg, 0) };
+    if sent < 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    Ok(sent as usize)
 }
 
 #[cfg(test)]
 mod tests {
+    /// Guards tests that mutate process-global `NOTIFY_SOCKET`/`WATCHDOG_*`
+    /// env vars, since cargo runs tests in parallel threads of one process.
+    fn env_lock() -> std::sync::MutexGuard<'static, ()> {
+        static LOCK: std::sync::OnceLock<std::sync::Mutex<()>> = std::sync::OnceLock::new();
+        LOCK.get_or_init(|| std::sync::Mutex::new(()))
+            .lock()
+            .unwrap_or_else(|poisoned| poisoned.into_inner())
+    }
+
     #[test]
     fn ok() {
         use super::*;
@@ -177,4 +655,87 @@ mod tests {
         listener.recv(&mut buf).unwrap();
         assert_eq!(&buf[..7], b"READY=1");
     }
+
+    #[test]
+    fn watchdog_enabled_parses_usec() {
+        use super::*;
+        let _guard = env_lock();
+
+        env::remove_var("WATCHDOG_PID");
+        env::set_var("WATCHDOG_USEC", "30000000");
+        assert_eq!(
+            SdNotify::watchdog_enabled(),
+            Some(std::time::Duration::from_secs(30))
+        );
+        env::remove_var("WATCHDOG_USEC");
+    }
+
+    #[test]
+    fn watchdog_enabled_none_without_usec() {
+        use super::*;
+        let _guard = env_lock();
+
+        env::remove_var("WATCHDOG_USEC");
+        env::remove_var("WATCHDOG_PID");
+        assert_eq!(SdNotify::watchdog_enabled(), None);
+    }
+
+    #[test]
+    fn watchdog_enabled_none_for_mismatched_pid() {
+        use super::*;
+        let _guard = env_lock();
+
+        env::set_var("WATCHDOG_USEC", "30000000");
+        env::set_var("WATCHDOG_PID", "1");
+        assert_ne!(std::process::id(), 1);
+        assert_eq!(SdNotify::watchdog_enabled(), None);
+        env::remove_var("WATCHDOG_USEC");
+        env::remove_var("WATCHDOG_PID");
+    }
+
+    #[test]
+    fn watchdog_enabled_some_for_matching_pid() {
+        use super::*;
+        let _guard = env_lock();
+
+        env::set_var("WATCHDOG_USEC", "1000000");
+        env::set_var("WATCHDOG_PID", std::process::id().to_string());
+        assert_eq!(
+            SdNotify::watchdog_enabled(),
+            Some(std::time::Duration::from_secs(1))
+        );
+        env::remove_var("WATCHDOG_USEC");
+        env::remove_var("WATCHDOG_PID");
+    }
+
+    #[test]
+    fn notify_is_noop_without_notify_socket() {
+        use super::*;
+        let _guard = env_lock();
+
+        env::remove_var("NOTIFY_SOCKET");
+        assert!(notify(false, &[State::Ready]).is_ok());
+    }
+
+    #[test]
+    fn notify_sends_batched_states_and_unsets_env() {
+        use super::*;
+        let _guard = env_lock();
+
+        let path = "/tmp/kek-notify-fn.sock";
+        let _ = std::fs::remove_file(path);
+        let listener = UnixDatagram::bind(path).unwrap();
+
+        env::set_var("NOTIFY_SOCKET", path);
+        env::set_var("WATCHDOG_USEC", "1000000");
+
+        notify(true, &[State::Ready, State::Status("ok".to_string())]).unwrap();
+
+        let mut buf = [0; 100];
+        let n = listener.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"READY=1\nSTATUS=ok");
+
+        assert!(env::var("NOTIFY_SOCKET").is_err());
+        assert!(env::var("WATCHDOG_USEC").is_err());
+    }
 }